@@ -2,9 +2,642 @@
 //!
 //! ## Features
 //! - Initializes GPIO input pins as buttons.
-//! - Configures pull-up resistor for stable button reads.
+//! - `Button` wrapper encoding pressed/released semantics in one place.
+//! - `Debounced` wrapper with a compile-time debounce window.
+//! - Configures pull-up or pull-down resistor for stable button reads.
+//! - Debounced press and release waits with configurable timing.
+//! - Active-low and active-high polarity support.
+//! - Short versus long press classification.
+//! - Multi-click counting within a rolling window.
+//! - Press duration measurement.
+//! - Hold-to-repeat tick generation.
+//! - Press waits with a timeout.
+//! - `ButtonArray` for managing several buttons uniformly, with bitmask
+//!   snapshots for chord detection.
+//! - Raw edge detection for custom protocols.
+//! - Majority-vote sampling for polling loops.
+//! - Falling-edge pulse counting over a time window.
+//! - `ButtonInput` trait with a scripted `MockButton` for host-side logic.
 
-use embassy_rp::gpio::{Input, Pin, Pull};
+#![allow(dead_code)]
+
+use embassy_futures::select::{Either, select, select_array};
+use embassy_rp::gpio::{Input, Level, Pin, Pull};
+use embassy_time::{Duration, Instant, Timer, with_timeout};
+
+/// Default debounce window in milliseconds.
+pub const DEFAULT_DEBOUNCE_MS: u64 = 20;
+
+/// Maximum number of clicks counted by `wait_for_clicks`.
+pub const MAX_CLICKS: u8 = 5;
+
+/// Electrical polarity of a button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum ButtonPolarity {
+    /// Button wired to GND with a pull-up, reads low when pressed.
+    ActiveLow,
+    /// Button wired to 3V3 with a pull-down, reads high when pressed.
+    ActiveHigh,
+}
+
+impl ButtonPolarity {
+    /// Returns the input level that corresponds to "pressed".
+    ///
+    /// # Example
+    /// ```ignore
+    /// let level = ButtonPolarity::ActiveLow.pressed_level();
+    /// ```
+    pub fn pressed_level(self) -> Level {
+        match self {
+            ButtonPolarity::ActiveLow => Level::Low,
+            ButtonPolarity::ActiveHigh => Level::High,
+        }
+    }
+
+    /// Returns the input level that corresponds to "released".
+    ///
+    /// # Example
+    /// ```ignore
+    /// let level = ButtonPolarity::ActiveLow.released_level();
+    /// ```
+    pub fn released_level(self) -> Level {
+        match self {
+            ButtonPolarity::ActiveLow => Level::High,
+            ButtonPolarity::ActiveHigh => Level::Low,
+        }
+    }
+}
+
+/// Classification of a single button press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum ButtonEvent {
+    /// Button was released before the long-press threshold.
+    ShortPress,
+    /// Button was still held when the long-press threshold elapsed.
+    LongPress,
+}
+
+/// Direction of a signal edge on an input pin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Edge {
+    /// Low to high transition.
+    Rising,
+    /// High to low transition.
+    Falling,
+}
+
+impl Edge {
+    /// Returns the level of the pin after the edge.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let level = button::wait_for_edge(&mut button).await.level();
+    /// ```
+    pub fn level(self) -> Level {
+        match self {
+            Edge::Rising => Level::High,
+            Edge::Falling => Level::Low,
+        }
+    }
+}
+
+/// Debounce windows used by `Button` for presses and releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct DebounceConfig {
+    /// Time the line must settle at the pressed level.
+    pub press: Duration,
+    /// Time the line must settle at the released level.
+    pub release: Duration,
+}
+
+impl Default for DebounceConfig {
+    /// Uses `DEFAULT_DEBOUNCE_MS` for both press and release.
+    fn default() -> Self {
+        Self {
+            press: default_debounce(),
+            release: default_debounce(),
+        }
+    }
+}
+
+/// Represents a push button on a GPIO input.
+pub struct Button {
+    input: Input<'static>,
+    polarity: ButtonPolarity,
+    debounce: DebounceConfig,
+}
+
+impl Button {
+    /// Initializes a button, inferring polarity from the pull resistor.
+    ///
+    /// # Arguments
+    /// * `pin` - Any GPIO pin implementing `Pin`.
+    /// * `pull` - Pull resistor to enable on the pin.
+    ///
+    /// # Returns
+    /// * `Button` - `Pull::Down` gives an active-high button, `Pull::Up` and
+    ///   `Pull::None` give an active-low button. With `Pull::None` and an
+    ///   external pull-down, use `with_polarity` instead.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let button = Button::new(p.PIN_16, Pull::Up);
+    /// ```
+    pub fn new(pin: impl Pin, pull: Pull) -> Self {
+        let polarity = match pull {
+            Pull::Down => ButtonPolarity::ActiveHigh,
+            _ => ButtonPolarity::ActiveLow,
+        };
+        Self::with_polarity(pin, pull, polarity)
+    }
+
+    /// Initializes a button with an explicit polarity.
+    ///
+    /// # Arguments
+    /// * `pin` - Any GPIO pin implementing `Pin`.
+    /// * `pull` - Pull resistor to enable on the pin.
+    /// * `polarity` - Whether the button reads low or high when pressed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Button to 3V3 with an external pull-down resistor.
+    /// let button = Button::with_polarity(p.PIN_16, Pull::None, ButtonPolarity::ActiveHigh);
+    /// ```
+    pub fn with_polarity(pin: impl Pin, pull: Pull, polarity: ButtonPolarity) -> Self {
+        Self {
+            input: init(pin, pull),
+            polarity,
+            debounce: DebounceConfig::default(),
+        }
+    }
+
+    /// Sets the debounce windows used by `wait_for_press`/`wait_for_release`.
+    ///
+    /// # Arguments
+    /// * `debounce` - New press and release debounce windows.
+    ///
+    /// # Example
+    /// ```ignore
+    /// button.set_debounce(DebounceConfig {
+    ///     press: Duration::from_millis(30),
+    ///     release: Duration::from_millis(50),
+    /// });
+    /// ```
+    pub fn set_debounce(&mut self, debounce: DebounceConfig) {
+        self.debounce = debounce;
+    }
+
+    /// Returns the debounce windows currently in use.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let debounce = button.debounce();
+    /// ```
+    pub fn debounce(&self) -> DebounceConfig {
+        self.debounce
+    }
+
+    /// Returns the polarity of the button.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let polarity = button.polarity();
+    /// ```
+    pub fn polarity(&self) -> ButtonPolarity {
+        self.polarity
+    }
+
+    /// Returns the underlying input for use with the free helper functions.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let event = button::wait_for_event(button.input(), threshold).await;
+    /// ```
+    pub fn input(&mut self) -> &mut Input<'static> {
+        &mut self.input
+    }
+
+    /// Returns `true` if the button is currently pressed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if button.is_pressed() { led.on(); }
+    /// ```
+    pub fn is_pressed(&self) -> bool {
+        self.input.get_level() == self.polarity.pressed_level()
+    }
+
+    /// Waits for a press debounced by `DebounceConfig::press`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// button.wait_for_press().await;
+    /// ```
+    pub async fn wait_for_press(&mut self) {
+        let level = self.polarity.pressed_level();
+        wait_for_level_debounced(&mut self.input, level, self.debounce.press).await;
+    }
+
+    /// Waits for a release that holds for the whole `DebounceConfig::release`
+    /// window.
+    ///
+    /// # Behavior
+    /// - A bounce back to pressed inside the window restarts the wait, so
+    ///   the press continues.
+    ///
+    /// # Example
+    /// ```ignore
+    /// button.wait_for_release().await;
+    /// ```
+    pub async fn wait_for_release(&mut self) {
+        let level = self.polarity.released_level();
+        wait_for_level_stable(&mut self.input, level, self.debounce.release).await;
+    }
+}
+
+/// Common press/release interface implemented by `Button`, `Debounced` and
+/// `MockButton`.
+///
+/// The futures carry no `Send` bound, which is fine for the single-threaded
+/// embassy executor this crate runs on.
+#[allow(async_fn_in_trait)]
+pub trait ButtonInput {
+    /// Waits for a debounced press.
+    async fn wait_for_press(&mut self);
+
+    /// Waits for a debounced release.
+    async fn wait_for_release(&mut self);
+
+    /// Returns `true` if the button is currently pressed.
+    fn is_pressed(&self) -> bool;
+}
+
+impl ButtonInput for Button {
+    async fn wait_for_press(&mut self) {
+        Button::wait_for_press(self).await;
+    }
+
+    async fn wait_for_release(&mut self) {
+        Button::wait_for_release(self).await;
+    }
+
+    fn is_pressed(&self) -> bool {
+        Button::is_pressed(self)
+    }
+}
+
+/// Active-low button whose debounce window of `MS` milliseconds is fixed at
+/// compile time.
+pub struct Debounced<const MS: u64> {
+    input: Input<'static>,
+}
+
+impl<const MS: u64> Debounced<MS> {
+    /// Debounce window baked into this type.
+    pub const DEBOUNCE: Duration = Duration::from_millis(MS);
+
+    /// Wraps an input configured with a pull-up.
+    ///
+    /// # Arguments
+    /// * `input` - Button input, e.g. from `init_pullup`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut button = Debounced::<30>::new(button::init_pullup(p.PIN_16));
+    /// ```
+    pub fn new(input: Input<'static>) -> Self {
+        Self { input }
+    }
+
+    /// Returns `true` if the button is currently pressed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if button.is_pressed() { led.on(); }
+    /// ```
+    pub fn is_pressed(&self) -> bool {
+        self.input.is_low()
+    }
+
+    /// Waits for a press debounced by `MS` milliseconds.
+    ///
+    /// # Example
+    /// ```ignore
+    /// button.wait_for_press().await;
+    /// ```
+    pub async fn wait_for_press(&mut self) {
+        wait_for_press_debounced(&mut self.input, Self::DEBOUNCE).await;
+    }
+
+    /// Waits for a release debounced by `MS` milliseconds.
+    ///
+    /// # Example
+    /// ```ignore
+    /// button.wait_for_release().await;
+    /// ```
+    pub async fn wait_for_release(&mut self) {
+        wait_for_release_debounced(&mut self.input, Self::DEBOUNCE).await;
+    }
+}
+
+impl<const MS: u64> ButtonInput for Debounced<MS> {
+    async fn wait_for_press(&mut self) {
+        Debounced::wait_for_press(self).await;
+    }
+
+    async fn wait_for_release(&mut self) {
+        Debounced::wait_for_release(self).await;
+    }
+
+    fn is_pressed(&self) -> bool {
+        Debounced::is_pressed(self)
+    }
+}
+
+/// Hardware-free button that replays a scripted sequence of states.
+#[cfg(any(test, feature = "mock"))]
+pub struct MockButton {
+    script: MockScript,
+    consumed: usize,
+}
+
+/// States replayed by a `MockButton`.
+#[cfg(any(test, feature = "mock"))]
+enum MockScript {
+    /// Logical pressed (`true`) / released (`false`) states.
+    Pressed(&'static [bool]),
+    /// Raw pin levels interpreted through a polarity.
+    Levels(&'static [Level], ButtonPolarity),
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl MockButton {
+    /// Creates a released mock button that will step through `script`.
+    ///
+    /// # Arguments
+    /// * `script` - Successive pressed (`true`) / released (`false`) states.
+    ///
+    /// # Behavior
+    /// - Each wait consumes states until the requested one is reached.
+    /// - Once the script is exhausted, further waits never complete.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut button = MockButton::new(&[true, false]);
+    /// ```
+    pub fn new(script: &'static [bool]) -> Self {
+        Self {
+            script: MockScript::Pressed(script),
+            consumed: 0,
+        }
+    }
+
+    /// Creates a released mock button that will step through raw pin levels.
+    ///
+    /// # Arguments
+    /// * `levels` - Successive pin levels, as the GPIO would read them.
+    /// * `polarity` - Which level counts as pressed.
+    ///
+    /// # Behavior
+    /// - Same as `new`, with each level mapped through `polarity`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut button =
+    ///     MockButton::with_polarity(&[Level::Low, Level::High], ButtonPolarity::ActiveLow);
+    /// ```
+    pub fn with_polarity(levels: &'static [Level], polarity: ButtonPolarity) -> Self {
+        Self {
+            script: MockScript::Levels(levels, polarity),
+            consumed: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.script {
+            MockScript::Pressed(states) => states.len(),
+            MockScript::Levels(levels, _) => levels.len(),
+        }
+    }
+
+    fn pressed_at(&self, index: usize) -> bool {
+        match self.script {
+            MockScript::Pressed(states) => states[index],
+            MockScript::Levels(levels, polarity) => levels[index] == polarity.pressed_level(),
+        }
+    }
+
+    async fn wait_for_state(&mut self, pressed: bool) {
+        while self.is_pressed() != pressed {
+            if self.consumed == self.len() {
+                core::future::pending::<()>().await;
+            }
+            self.consumed += 1;
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl ButtonInput for MockButton {
+    async fn wait_for_press(&mut self) {
+        self.wait_for_state(true).await;
+    }
+
+    async fn wait_for_release(&mut self) {
+        self.wait_for_state(false).await;
+    }
+
+    fn is_pressed(&self) -> bool {
+        self.consumed
+            .checked_sub(1)
+            .is_some_and(|index| self.pressed_at(index))
+    }
+}
+
+/// Represents a fixed set of active-low buttons.
+pub struct ButtonArray<const N: usize> {
+    buttons: [Input<'static>; N],
+}
+
+impl<const N: usize> ButtonArray<N> {
+    /// Creates a button array from already initialized inputs.
+    ///
+    /// # Arguments
+    /// * `buttons` - Inputs configured with pull-ups, e.g. via `init_pullup`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let buttons = ButtonArray::new([
+    ///     button::init_pullup(p.PIN_16),
+    ///     button::init_pullup(p.PIN_17),
+    ///     button::init_pullup(p.PIN_18),
+    /// ]);
+    /// ```
+    pub fn new(buttons: [Input<'static>; N]) -> Self {
+        Self { buttons }
+    }
+
+    /// Returns `true` if the button at `index` is currently pressed.
+    ///
+    /// # Arguments
+    /// * `index` - Button index; out-of-range indices return `false`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if buttons.is_pressed(0) { led.on(); }
+    /// ```
+    pub fn is_pressed(&self, index: usize) -> bool {
+        self.buttons.get(index).is_some_and(|b| b.is_low())
+    }
+
+    /// Waits until any button is newly pressed and returns its index.
+    ///
+    /// # Returns
+    /// * `usize` - Index of the first button that went low.
+    ///
+    /// # Behavior
+    /// - Only falling edges count, so a button already held at entry is
+    ///   not reported until it is released and pressed again.
+    /// - The press is confirmed after `DEFAULT_DEBOUNCE_MS`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mode = buttons.wait_for_any_press().await;
+    /// ```
+    pub async fn wait_for_any_press(&mut self) -> usize {
+        loop {
+            let futures = self.buttons.each_mut().map(|b| b.wait_for_falling_edge());
+            let ((), index) = select_array(futures).await;
+
+            Timer::after(default_debounce()).await;
+            if self.buttons[index].is_low() {
+                return index;
+            }
+        }
+    }
+
+    /// Returns the pressed state of every button as a bitmask.
+    ///
+    /// # Returns
+    /// * `u32` - Bit `i` is set if button `i` is pressed (reads low).
+    ///
+    /// # Behavior
+    /// - Supports up to 32 buttons; larger `N` fails to compile.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if buttons.snapshot() == 0b011 { /* buttons 0 and 1 held together */ }
+    /// ```
+    pub fn snapshot(&self) -> u32 {
+        const { assert!(N <= 32, "ButtonArray::snapshot supports at most 32 buttons") };
+
+        self.buttons
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.is_low())
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    /// Waits until every button in `chord` is held together for `settle`.
+    ///
+    /// # Arguments
+    /// * `chord` - Bitmask of buttons that must be pressed, as in `snapshot`.
+    /// * `settle` - Time the chord must be held without any button changing.
+    ///
+    /// # Returns
+    /// * `true` - The chord was held for `settle`.
+    /// * `false` - `chord` names buttons outside `0..N` and can never match.
+    ///
+    /// # Behavior
+    /// - Extra buttons outside `chord` may also be held.
+    /// - Any edge on any button restarts the settle window, so keys pressed
+    ///   not-quite-simultaneously still register once they all settle.
+    ///
+    /// # Example
+    /// ```ignore
+    /// buttons.wait_for_chord(0b101, Duration::from_millis(200)).await;
+    /// ```
+    pub async fn wait_for_chord(&mut self, chord: u32, settle: Duration) -> bool {
+        let all = if N >= 32 { u32::MAX } else { (1 << N) - 1 };
+        if chord & !all != 0 {
+            return false;
+        }
+
+        loop {
+            if self.snapshot() & chord == chord
+                && with_timeout(settle, self.wait_for_any_edge())
+                    .await
+                    .is_err()
+            {
+                return true;
+            }
+            if self.snapshot() & chord != chord {
+                self.wait_for_any_edge().await;
+            }
+        }
+    }
+
+    /// Like `wait_for_chord`, but gives up after `timeout`.
+    ///
+    /// # Returns
+    /// * `true` - The chord was held for `settle` within `timeout`.
+    /// * `false` - The timeout elapsed first, or the chord is impossible.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let unlocked = buttons
+    ///     .wait_for_chord_timeout(0b011, Duration::from_millis(200), Duration::from_secs(5))
+    ///     .await;
+    /// ```
+    pub async fn wait_for_chord_timeout(
+        &mut self,
+        chord: u32,
+        settle: Duration,
+        timeout: Duration,
+    ) -> bool {
+        with_timeout(timeout, self.wait_for_chord(chord, settle))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn wait_for_any_edge(&mut self) {
+        let futures = self.buttons.each_mut().map(|b| b.wait_for_any_edge());
+        select_array(futures).await;
+    }
+}
+
+/// Returns `DEFAULT_DEBOUNCE_MS` as a `Duration`.
+///
+/// # Example
+/// ```ignore
+/// button::wait_for_press_debounced(&mut button, button::default_debounce()).await;
+/// ```
+pub fn default_debounce() -> Duration {
+    Duration::from_millis(DEFAULT_DEBOUNCE_MS)
+}
+
+/// Initializes a button input pin with the given pull resistor.
+///
+/// # Arguments
+/// * `pin` - Any GPIO pin implementing `Pin`.
+/// * `pull` - Pull resistor to enable on the pin.
+///
+/// # Returns
+/// * `Input<'static>` - Configured input.
+///
+/// # Pressed Level
+/// - `Pull::Up` - Button wired to GND, reads low when pressed (active-low).
+/// - `Pull::Down` - Button wired to 3V3, reads high when pressed (active-high).
+/// - `Pull::None` - Depends on the external resistor; the pressed level is
+///   the opposite of the level the external resistor pulls to.
+///
+/// # Example
+/// ```ignore
+/// let button = button::init(p.PIN_16, Pull::Down);
+/// ```
+pub fn init(pin: impl Pin, pull: Pull) -> Input<'static> {
+    Input::new(pin, pull)
+}
 
 /// Initializes a button input pin with a pull-up resistor.
 ///
@@ -12,12 +645,432 @@ use embassy_rp::gpio::{Input, Pin, Pull};
 /// * `pin` - Any GPIO pin implementing `Pin`.
 ///
 /// # Returns
-/// * `Input<'static>` - Configured input with pull-up.
+/// * `Input<'static>` - Configured input with pull-up (active-low).
+///
+/// # Example
+/// ```ignore
+/// let button = button::init_pullup(p.PIN_16);
+/// ```
+pub fn init_pullup(pin: impl Pin) -> Input<'static> {
+    init(pin, Pull::Up)
+}
+
+/// Waits for the input to settle at a given level.
+///
+/// # Arguments
+/// * `button` - Button input.
+/// * `level` - Level to wait for.
+/// * `debounce` - Time to wait before sampling the line again.
+///
+/// # Behavior
+/// - Waits for the line to reach `level`.
+/// - Samples the line again after `debounce`.
+/// - Returns only if the line is still at `level`, otherwise keeps waiting.
+///
+/// # Example
+/// ```ignore
+/// button::wait_for_level_debounced(&mut button, Level::High, debounce).await;
+/// ```
+pub async fn wait_for_level_debounced(button: &mut Input<'_>, level: Level, debounce: Duration) {
+    loop {
+        wait_for_level(button, level).await;
+        Timer::after(debounce).await;
+        if button.get_level() == level {
+            return;
+        }
+    }
+}
+
+/// Waits for the input to stay at a given level for a whole window.
+///
+/// # Arguments
+/// * `button` - Button input.
+/// * `level` - Level to wait for.
+/// * `window` - Time the line must stay at `level` before it is accepted.
+///
+/// # Behavior
+/// - Waits for the line to reach `level`.
+/// - Restarts if the line leaves `level` at any point during `window`,
+///   so a brief bounce inside the window is not mistaken for a settle.
+///
+/// # Example
+/// ```ignore
+/// button::wait_for_level_stable(&mut button, Level::High, window).await;
+/// ```
+pub async fn wait_for_level_stable(button: &mut Input<'_>, level: Level, window: Duration) {
+    loop {
+        wait_for_level(button, level).await;
+        if with_timeout(window, wait_for_level(button, opposite(level)))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Returns the other logic level.
+fn opposite(level: Level) -> Level {
+    match level {
+        Level::Low => Level::High,
+        Level::High => Level::Low,
+    }
+}
+
+/// Waits for the input to reach `level`, without debouncing.
+async fn wait_for_level(button: &mut Input<'_>, level: Level) {
+    match level {
+        Level::Low => button.wait_for_low().await,
+        Level::High => button.wait_for_high().await,
+    }
+}
+
+/// Waits for a debounced button press (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `debounce` - Time the line must settle low before the press is accepted.
+///
+/// # Example
+/// ```ignore
+/// button::wait_for_press_debounced(&mut button, Duration::from_millis(DEFAULT_DEBOUNCE_MS)).await;
+/// ```
+pub async fn wait_for_press_debounced(button: &mut Input<'_>, debounce: Duration) {
+    wait_for_level_debounced(button, Level::Low, debounce).await;
+}
+
+/// Waits for a debounced button release (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `debounce` - Time the line must settle high before the release is accepted.
+///
+/// # Example
+/// ```ignore
+/// button::wait_for_release_debounced(&mut button, Duration::from_millis(DEFAULT_DEBOUNCE_MS)).await;
+/// ```
+pub async fn wait_for_release_debounced(button: &mut Input<'_>, debounce: Duration) {
+    wait_for_level_debounced(button, Level::High, debounce).await;
+}
+
+/// Waits for a press and classifies it as short or long (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `long_press_threshold` - Hold time at which a press counts as long.
+///
+/// # Returns
+/// * `ButtonEvent::ShortPress` - Released before the threshold.
+/// * `ButtonEvent::LongPress` - Still held when the threshold elapsed.
+///
+/// # Behavior
+/// - If the button is already held at entry, waits for release first.
+/// - Press and release are debounced with `DEFAULT_DEBOUNCE_MS`, so contact
+///   bounce is never reported as a short press.
+/// - The threshold is measured from the debounced press and raced against
+///   the first release edge; a release that starts before the threshold is
+///   a short press even if its debounce window ends after it.
+/// - A long press is reported as soon as the threshold elapses, while
+///   the button may still be held.
+///
+/// # Example
+/// ```ignore
+/// let event = button::wait_for_event(&mut button, Duration::from_millis(800)).await;
+/// ```
+pub async fn wait_for_event(button: &mut Input<'_>, long_press_threshold: Duration) -> ButtonEvent {
+    let debounce = default_debounce();
+
+    if button.is_low() {
+        wait_for_release_debounced(button, debounce).await;
+    }
+
+    wait_for_press_debounced(button, debounce).await;
+
+    let deadline = Instant::now() + long_press_threshold;
+    if release_before(button, deadline, debounce).await {
+        ButtonEvent::ShortPress
+    } else {
+        ButtonEvent::LongPress
+    }
+}
+
+/// Waits for a release edge before `deadline` and confirms it (active-low).
+///
+/// Returns `true` once the line goes high before `deadline` and then stays
+/// high for `debounce`. The edge is checked before the deadline, so a
+/// release wins a tie. The confirmation is not cut short by `deadline`; if
+/// the line bounces low again, waiting resumes against the same deadline.
+async fn release_before(button: &mut Input<'_>, deadline: Instant, debounce: Duration) -> bool {
+    loop {
+        match select(button.wait_for_high(), Timer::at(deadline)).await {
+            Either::First(()) => {
+                if with_timeout(debounce, button.wait_for_low()).await.is_err() {
+                    return true;
+                }
+            }
+            Either::Second(()) => return false,
+        }
+    }
+}
+
+/// Counts consecutive clicks separated by less than `window` (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `window` - Maximum gap between a release and the next press.
+///
+/// # Returns
+/// * `u8` - Number of clicks, from 1 up to `MAX_CLICKS`.
+///
+/// # Behavior
+/// - If the button is already held at entry, waits for release first.
+/// - Each press and release is debounced with `DEFAULT_DEBOUNCE_MS`.
+/// - The window starts only after each click is released, so a slow
+///   first press never eats into the gap allowed for the second.
+/// - The next press must settle inside the window, so `window` has to be
+///   longer than `DEFAULT_DEBOUNCE_MS`; a shorter window never counts a
+///   second click.
+/// - Returns as soon as `MAX_CLICKS` is reached.
+///
+/// # Example
+/// ```ignore
+/// if button::wait_for_clicks(&mut button, Duration::from_millis(300)).await == 2 {
+///     // double-click
+/// }
+/// ```
+pub async fn wait_for_clicks(button: &mut Input<'_>, window: Duration) -> u8 {
+    let debounce = default_debounce();
+
+    if button.is_low() {
+        wait_for_release_debounced(button, debounce).await;
+    }
+
+    wait_for_press_debounced(button, debounce).await;
+    wait_for_release_debounced(button, debounce).await;
+
+    let mut clicks = 1;
+
+    while clicks < MAX_CLICKS {
+        if with_timeout(window, wait_for_press_debounced(button, debounce))
+            .await
+            .is_err()
+        {
+            break;
+        }
+        wait_for_release_debounced(button, debounce).await;
+        clicks += 1;
+    }
+
+    clicks
+}
+
+/// Measures how long the next full press is held (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+///
+/// # Returns
+/// * `Duration` - Time between the debounced press and the debounced release.
+///
+/// # Behavior
+/// - If the button is already held at entry, waits for release first and
+///   then measures the next full press.
+/// - Press and release are debounced with `DEFAULT_DEBOUNCE_MS`; timing
+///   starts once the press is confirmed, so bounce is never measured.
+///
+/// # Example
+/// ```ignore
+/// let held = button::timed_press(&mut button).await;
+/// ```
+pub async fn timed_press(button: &mut Input<'_>) -> Duration {
+    let debounce = default_debounce();
+
+    if button.is_low() {
+        wait_for_release_debounced(button, debounce).await;
+    }
+
+    wait_for_press_debounced(button, debounce).await;
+    let start = Instant::now();
+
+    wait_for_release_debounced(button, debounce).await;
+    Instant::now() - start
+}
+
+/// Calls `on_tick` repeatedly while the button is held (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `initial_delay` - Hold time before the first tick.
+/// * `interval` - Time between subsequent ticks.
+/// * `on_tick` - Callback invoked with the zero-based tick index.
+///
+/// # Returns
+/// * `u32` - Total number of ticks generated.
+///
+/// # Behavior
+/// - Waits for a debounced press; a button already held at entry is
+///   accepted once it still reads down after `DEFAULT_DEBOUNCE_MS`.
+/// - Ticks run on a fixed schedule from the press and stop at the first
+///   release edge; the function returns once the line has then stayed
+///   released for `DEFAULT_DEBOUNCE_MS`.
+/// - If the release was a bounce, ticking resumes on the same schedule.
+/// - With an `interval` shorter than `DEFAULT_DEBOUNCE_MS`, ticks that fall
+///   due while a release is being confirmed are skipped if the release
+///   holds, or fired back-to-back if it turns out to be a bounce.
 ///
 /// # Example
 /// ```ignore
-/// let button = button::init(p.PIN_16);
+/// let ticks = button::hold_repeat(&mut button, Duration::from_millis(500), Duration::from_millis(100), |i| {
+///     brightness = brightness.saturating_add(1);
+/// })
+/// .await;
 /// ```
-pub fn init(pin: impl Pin) -> Input<'static> {
-    Input::new(pin, Pull::Up)
+pub async fn hold_repeat<F>(
+    button: &mut Input<'_>,
+    initial_delay: Duration,
+    interval: Duration,
+    mut on_tick: F,
+) -> u32
+where
+    F: FnMut(u32),
+{
+    let debounce = default_debounce();
+
+    wait_for_press_debounced(button, debounce).await;
+
+    let mut ticks = 0;
+    let mut deadline = Instant::now() + initial_delay;
+
+    while !release_before(button, deadline, debounce).await {
+        on_tick(ticks);
+        ticks += 1;
+        deadline += interval;
+    }
+
+    ticks
+}
+
+/// Waits for a debounced press, giving up after `timeout` (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `timeout` - Maximum time to wait for the press.
+///
+/// # Returns
+/// * `true` - A press occurred within the timeout.
+/// * `false` - The timeout elapsed first.
+///
+/// # Behavior
+/// - If the button is already held at entry, it must be released and
+///   pressed again within the timeout to count.
+/// - A timeout only drops the pending level wait, so the next call
+///   starts from the current pin state as usual.
+///
+/// # Example
+/// ```ignore
+/// if !button::wait_for_press_timeout(&mut button, Duration::from_secs(5)).await {
+///     // no input, do something else
+/// }
+/// ```
+pub async fn wait_for_press_timeout(button: &mut Input<'_>, timeout: Duration) -> bool {
+    let debounce = default_debounce();
+
+    with_timeout(timeout, async {
+        if button.is_low() {
+            wait_for_release_debounced(button, debounce).await;
+        }
+        wait_for_press_debounced(button, debounce).await;
+    })
+    .await
+    .is_ok()
+}
+
+/// Waits for the next rising or falling edge (no debouncing).
+///
+/// # Arguments
+/// * `button` - Button input.
+///
+/// # Returns
+/// * `Edge` - Direction of the edge; `Edge::level` gives the new level.
+///
+/// # Behavior
+/// - The direction is taken from the level read right after the edge, so
+///   pulses shorter than the task wake-up latency may be misreported.
+///
+/// # Example
+/// ```ignore
+/// match button::wait_for_edge(&mut button).await {
+///     Edge::Falling => start = Instant::now(),
+///     Edge::Rising => durations.push(Instant::now() - start),
+/// }
+/// ```
+pub async fn wait_for_edge(button: &mut Input<'_>) -> Edge {
+    button.wait_for_any_edge().await;
+
+    if button.is_high() {
+        Edge::Rising
+    } else {
+        Edge::Falling
+    }
+}
+
+/// Samples the button several times and returns the majority state (active-low).
+///
+/// # Arguments
+/// * `button` - Button input with pull-up.
+/// * `samples` - Number of reads; use an odd count to avoid ties.
+/// * `interval` - Delay between consecutive reads.
+///
+/// # Returns
+/// * `true` - More than half of the samples read pressed (low).
+/// * `false` - Otherwise, including ties and `samples == 0`.
+///
+/// # Example
+/// ```ignore
+/// let pressed = button::read_debounced(&mut button, 5, Duration::from_millis(2)).await;
+/// ```
+pub async fn read_debounced(button: &mut Input<'_>, samples: u8, interval: Duration) -> bool {
+    let mut pressed = 0u8;
+
+    for sample in 0..samples {
+        if sample > 0 {
+            Timer::after(interval).await;
+        }
+        if button.is_low() {
+            pressed += 1;
+        }
+    }
+
+    pressed > samples / 2
+}
+
+/// Counts falling edges on the pin during `window` (no debouncing).
+///
+/// # Arguments
+/// * `button` - Input pin, e.g. a reed switch or encoder detent.
+/// * `window` - Counting window, starting when the function is called.
+///
+/// # Returns
+/// * `u32` - Number of falling edges seen before the window elapsed.
+///
+/// # Behavior
+/// - Each edge wakes the task and re-arms the GPIO interrupt, so pulses
+///   closer together than that latency are merged. Expect reliable counts
+///   up to a few kHz, less when other tasks keep the executor busy.
+/// - Contact bounce is counted as extra edges.
+///
+/// # Example
+/// ```ignore
+/// let pulses = button::count_edges(&mut button, Duration::from_secs(1)).await;
+/// ```
+pub async fn count_edges(button: &mut Input<'_>, window: Duration) -> u32 {
+    let mut timer = Timer::after(window);
+    let mut edges = 0;
+
+    loop {
+        match select(button.wait_for_falling_edge(), &mut timer).await {
+            Either::First(()) => edges += 1,
+            Either::Second(()) => return edges,
+        }
+    }
 }
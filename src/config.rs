@@ -0,0 +1,81 @@
+//! # Device Configuration Module
+//!
+//! ## Features
+//! - Enables the RP2040 watchdog with a bounded timeout.
+//! - Heartbeat that toggles the onboard LED and feeds the watchdog.
+
+#![allow(dead_code)]
+
+use crate::led::Led;
+use embassy_rp::peripherals::WATCHDOG;
+use embassy_rp::watchdog::Watchdog;
+use embassy_time::Duration;
+
+/// Longest timeout the RP2040 watchdog supports, in microseconds.
+pub const MAX_WATCHDOG_TIMEOUT_US: u64 = 0x7F_FFFF;
+
+/// Starts the watchdog so the chip resets if it is not fed in time.
+///
+/// # Arguments
+/// * `wdt` - The `WATCHDOG` peripheral.
+/// * `timeout` - Time allowed between feeds, clamped to
+///   `MAX_WATCHDOG_TIMEOUT_US` (about 8.3 s).
+///
+/// # Returns
+/// * `Watchdog` - Running watchdog; feed it with `heartbeat` or `feed`.
+///
+/// # Behavior
+/// - The watchdog pauses while a debugger halts the core, so stepping
+///   through code with GDB does not reset the chip.
+///
+/// # Example
+/// ```ignore
+/// let mut watchdog = config::enable_watchdog(p.WATCHDOG, Duration::from_secs(2));
+/// ```
+pub fn enable_watchdog(wdt: WATCHDOG, timeout: Duration) -> Watchdog {
+    let timeout = Duration::from_micros(timeout.as_micros().min(MAX_WATCHDOG_TIMEOUT_US));
+    let mut watchdog = Watchdog::new(wdt);
+    watchdog.pause_on_debug(true);
+    watchdog.start(timeout);
+    watchdog
+}
+
+/// Toggles the onboard LED and feeds the watchdog.
+///
+/// # Arguments
+/// * `watchdog` - Running watchdog from `enable_watchdog`.
+/// * `led` - Onboard LED used as a liveness indicator.
+///
+/// # Behavior
+/// - Call once per main-loop iteration; a steadily blinking LED means the
+///   loop is alive.
+/// - Toggles the same LED that `run_cycle` drives, so a cycle's on/off
+///   replaces one heartbeat blink and the heartbeat resumes from whatever
+///   state the cycle left.
+/// - Button waits such as `run_cycle` block until the user acts, so an
+///   idle loop would starve the watchdog. Bound every wait below the
+///   timeout: wait with `run_cycle::wait_press_or_idle`, finish a press with
+///   `run_cycle::run_cycle_from_press` under `with_timeout`, and keep the
+///   two bounds together shorter than the watchdog timeout.
+///
+/// # Example
+/// ```ignore
+/// let mut watchdog = config::enable_watchdog(p.WATCHDOG, Duration::from_secs(4));
+/// loop {
+///     config::heartbeat(&mut watchdog, &mut led);
+///     if run_cycle::wait_press_or_idle(&mut button, Duration::from_secs(1)).await
+///         == Activity::Pressed
+///     {
+///         // A press held past the bound is picked up again next iteration.
+///         let _ = with_timeout(
+///             Duration::from_secs(1),
+///             run_cycle::run_cycle_from_press(&mut led, &mut button),
+///         )
+///         .await;
+///     }
+/// }
+/// ```
+pub fn heartbeat(watchdog: &mut Watchdog, led: &mut Led) {
+    led.toggle();
+    watchdog.feed();
+}
@@ -2,16 +2,38 @@
 //!
 //! ## Features
 //! - Initialize onboard LED on configurable GPIO pin.
-//! - Turn the LED on and off.
+//! - Turn the LED on and off, for active-high or active-low wiring.
+//! - Set the LED directly from a boolean.
 //! - Toggle the LED state.
+//! - Query the current LED state.
+//! - Blink the LED a fixed number of times or forever.
+//! - Flash the LED briefly and restore its previous state.
+//! - Turns the LED off when dropped.
+//! - `embedded_hal` `OutputPin`/`StatefulOutputPin` adapter for `Led`.
+//! - PWM brightness control and breathing effect via `PwmLed`.
+//! - Independent blink task with a signal-controlled rate.
+//! - `LedControl` trait with a recording `MockLed` for host-side logic.
 
 #![allow(dead_code)]
 
+use embassy_futures::select::{Either, select};
+use embassy_rp::Peripheral;
+use embassy_rp::clocks::clk_sys_freq;
 use embassy_rp::gpio::{Level, Output, Pin};
+use embassy_rp::pwm::{ChannelBPin, Config, Pwm, Slice};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{Duration, Timer};
+use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
+
+/// Signal used to change the blink period of a running `blink_task`.
+pub type BlinkSignal = Signal<CriticalSectionRawMutex, Duration>;
 
 /// Represents an onboard LED controller.
 pub struct Led {
     led: Output<'static>,
+    is_on: bool,
+    active_low: bool,
 }
 
 impl Led {
@@ -28,8 +50,50 @@ impl Led {
     /// let led = Led::new(led_pin);
     /// ```
     pub fn new(led_pin: impl Pin) -> Self {
-        let led = Output::new(led_pin, Level::Low);
-        Self { led }
+        Self::init(led_pin, false, false)
+    }
+
+    /// Initializes the onboard LED in a caller-chosen state.
+    ///
+    /// # Arguments
+    /// * `led_pin` - Pin to control the onboard LED (must implement `Pin`).
+    /// * `on` - Whether the LED starts lit.
+    ///
+    /// # Returns
+    /// * `Led` instance with the pin already driven to the requested state.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let power = Led::new_with_state(led_pin, true);
+    /// ```
+    pub fn new_with_state(led_pin: impl Pin, on: bool) -> Self {
+        Self::init(led_pin, on, false)
+    }
+
+    /// Initializes an LED that lights when the pin is driven low.
+    ///
+    /// # Arguments
+    /// * `led_pin` - Pin sinking the LED current (must implement `Pin`).
+    ///
+    /// # Returns
+    /// * `Led` instance starting off, with the pin driven high so the LED
+    ///   never flashes at boot.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let led = Led::new_active_low(led_pin);
+    /// ```
+    pub fn new_active_low(led_pin: impl Pin) -> Self {
+        Self::init(led_pin, false, true)
+    }
+
+    fn init(led_pin: impl Pin, on: bool, active_low: bool) -> Self {
+        let led = Output::new(led_pin, level_for(on, active_low));
+        Self {
+            led,
+            is_on: on,
+            active_low,
+        }
     }
 
     /// Turns the LED on.
@@ -39,7 +103,10 @@ impl Led {
     /// led.on();
     /// ```
     pub fn on(&mut self) {
-        self.led.set_high();
+        self.led.set_level(level_for(true, self.active_low));
+        self.is_on = true;
+        #[cfg(feature = "verbose-logs")]
+        defmt::debug!("led: on");
     }
 
     /// Turns the LED off.
@@ -49,7 +116,10 @@ impl Led {
     /// led.off();
     /// ```
     pub fn off(&mut self) {
-        self.led.set_low();
+        self.led.set_level(level_for(false, self.active_low));
+        self.is_on = false;
+        #[cfg(feature = "verbose-logs")]
+        defmt::debug!("led: off");
     }
 
     /// Toggles the LED state.
@@ -60,5 +130,427 @@ impl Led {
     /// ```
     pub fn toggle(&mut self) {
         self.led.toggle();
+        self.is_on = !self.is_on;
+        #[cfg(feature = "verbose-logs")]
+        defmt::debug!("led: toggled, on = {}", self.is_on);
+    }
+
+    /// Sets the LED on or off from a boolean.
+    ///
+    /// # Arguments
+    /// * `on` - `true` turns the LED on, `false` turns it off.
+    ///
+    /// # Example
+    /// ```ignore
+    /// led.set(button.is_pressed());
+    /// ```
+    pub fn set(&mut self, on: bool) {
+        if on {
+            self.on();
+        } else {
+            self.off();
+        }
+    }
+
+    /// Returns `true` if the LED is currently on.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if led.is_on() { led.off(); }
+    /// ```
+    pub fn is_on(&self) -> bool {
+        self.is_on
+    }
+
+    /// Blinks the LED a fixed number of times, ending in the off state.
+    ///
+    /// # Arguments
+    /// * `times` - Number of on/off cycles; `0` is a no-op.
+    /// * `on` - Time the LED stays on in each cycle.
+    /// * `off` - Time the LED stays off in each cycle.
+    ///
+    /// # Cancellation
+    /// The pin and the cached `is_on` state are only changed together
+    /// between awaits, so dropping the future mid-blink leaves `is_on`
+    /// matching the pin. The LED may be left on in that case.
+    ///
+    /// # Example
+    /// ```ignore
+    /// led.blink(3, Duration::from_millis(100), Duration::from_millis(100)).await;
+    /// ```
+    pub async fn blink(&mut self, times: u32, on: Duration, off: Duration) {
+        for _ in 0..times {
+            self.on();
+            Timer::after(on).await;
+            self.off();
+            Timer::after(off).await;
+        }
+    }
+
+    /// Turns the LED on for `duration`, then restores its previous state.
+    ///
+    /// # Arguments
+    /// * `duration` - How long the LED stays on.
+    ///
+    /// # Cancellation
+    /// The previous state is restored by a drop guard, so dropping the
+    /// future mid-flash does not leave the LED stuck on.
+    ///
+    /// # Example
+    /// ```ignore
+    /// led.flash(Duration::from_millis(50)).await;
+    /// ```
+    pub async fn flash(&mut self, duration: Duration) {
+        let guard = RestoreState {
+            was_on: self.is_on,
+            led: self,
+        };
+        guard.led.on();
+        Timer::after(duration).await;
+    }
+
+    /// Blinks the LED forever with the given period.
+    ///
+    /// # Arguments
+    /// * `period` - Full on/off period; the LED toggles every `period / 2`.
+    ///
+    /// # Behavior
+    /// - Never returns, so run it from its own embassy task.
+    /// - Waits with `Timer::after`, yielding to the executor between toggles.
+    ///
+    /// # Example
+    /// ```ignore
+    /// #[embassy_executor::task]
+    /// async fn status(mut led: Led) {
+    ///     led.blink_forever(Duration::from_millis(500)).await
+    /// }
+    /// ```
+    pub async fn blink_forever(&mut self, period: Duration) -> ! {
+        let half = period / 2;
+        loop {
+            self.toggle();
+            Timer::after(half).await;
+        }
+    }
+}
+
+/// Drives the LED to its off level when the controller goes out of scope.
+///
+/// This is a best-effort clear: embassy then releases the pin to its reset
+/// configuration, and nothing here survives a chip reset.
+impl Drop for Led {
+    fn drop(&mut self) {
+        self.off();
+    }
+}
+
+/// `Led` never fails to drive its pin.
+impl ErrorType for Led {
+    type Error = core::convert::Infallible;
+}
+
+/// Drives the LED through `embedded_hal`, where high means on.
+///
+/// Levels are logical, so `set_high` lights an active-low LED too.
+impl OutputPin for Led {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.off();
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.on();
+        Ok(())
+    }
+}
+
+/// Reports the cached LED state through `embedded_hal`.
+impl StatefulOutputPin for Led {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_on())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_on())
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        Led::toggle(self);
+        Ok(())
+    }
+}
+
+/// Restores an LED to a saved state when dropped.
+struct RestoreState<'a> {
+    led: &'a mut Led,
+    was_on: bool,
+}
+
+impl Drop for RestoreState<'_> {
+    fn drop(&mut self) {
+        self.led.set(self.was_on);
+    }
+}
+
+/// Returns the pin level that puts an LED in the requested state.
+fn level_for(on: bool, active_low: bool) -> Level {
+    if on != active_low {
+        Level::High
+    } else {
+        Level::Low
+    }
+}
+
+/// Common on/off control implemented by the real `Led` and `MockLed`.
+pub trait LedControl {
+    /// Turns the LED on.
+    fn on(&mut self);
+
+    /// Turns the LED off.
+    fn off(&mut self);
+
+    /// Toggles the LED state.
+    fn toggle(&mut self);
+
+    /// Returns `true` if the LED is currently on.
+    fn is_on(&self) -> bool;
+}
+
+impl LedControl for Led {
+    fn on(&mut self) {
+        Led::on(self);
+    }
+
+    fn off(&mut self) {
+        Led::off(self);
+    }
+
+    fn toggle(&mut self) {
+        Led::toggle(self);
+    }
+
+    fn is_on(&self) -> bool {
+        Led::is_on(self)
+    }
+}
+
+/// Maximum number of transitions a `MockLed` records.
+#[cfg(any(test, feature = "mock"))]
+pub const MOCK_HISTORY_LEN: usize = 32;
+
+/// Hardware-free LED that records every state transition.
+#[cfg(any(test, feature = "mock"))]
+pub struct MockLed {
+    is_on: bool,
+    history: [bool; MOCK_HISTORY_LEN],
+    len: usize,
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl MockLed {
+    /// Creates a mock LED in the off state with an empty history.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut led = MockLed::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            is_on: false,
+            history: [false; MOCK_HISTORY_LEN],
+            len: 0,
+        }
+    }
+
+    /// Returns the recorded states, oldest first.
+    ///
+    /// # Behavior
+    /// - Transitions beyond `MOCK_HISTORY_LEN` are not recorded.
+    ///
+    /// # Example
+    /// ```ignore
+    /// assert_eq!(led.transitions(), &[true, false]);
+    /// ```
+    pub fn transitions(&self) -> &[bool] {
+        &self.history[..self.len]
+    }
+
+    fn record(&mut self, on: bool) {
+        self.is_on = on;
+        if self.len < MOCK_HISTORY_LEN {
+            self.history[self.len] = on;
+            self.len += 1;
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl Default for MockLed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl LedControl for MockLed {
+    fn on(&mut self) {
+        self.record(true);
+    }
+
+    fn off(&mut self) {
+        self.record(false);
+    }
+
+    fn toggle(&mut self) {
+        self.record(!self.is_on);
+    }
+
+    fn is_on(&self) -> bool {
+        self.is_on
+    }
+}
+
+/// Blinks the LED forever in its own task, adjustable at runtime.
+///
+/// # Arguments
+/// * `led` - LED to blink; the task takes ownership of it.
+/// * `period` - Signal carrying new blink periods from other tasks.
+/// * `initial` - Blink period used until the first signal arrives.
+///
+/// # Behavior
+/// - Toggles the LED every half period.
+/// - A new period takes effect immediately, without waiting for the
+///   current half period to finish.
+///
+/// # Example
+/// ```ignore
+/// static BLINK: BlinkSignal = BlinkSignal::new();
+///
+/// spawner.spawn(led::blink_task(led, &BLINK, Duration::from_millis(500))).unwrap();
+/// BLINK.signal(Duration::from_millis(100));
+/// ```
+#[embassy_executor::task]
+pub async fn blink_task(mut led: Led, period: &'static BlinkSignal, initial: Duration) {
+    let mut current = initial;
+    loop {
+        led.toggle();
+        if let Either::Second(new) = select(Timer::after(current / 2), period.wait()).await {
+            current = new;
+        }
+    }
+}
+
+/// Default PWM frequency for `PwmLed`, high enough to avoid visible flicker.
+pub const DEFAULT_PWM_FREQUENCY_HZ: u32 = 1_000;
+
+/// Number of brightness steps in one `PwmLed::breathe` period.
+pub const BREATHE_STEPS: u32 = 50;
+
+/// Represents an LED driven by a PWM slice for brightness control.
+pub struct PwmLed {
+    pwm: Pwm<'static>,
+    config: Config,
+    brightness: u8,
+}
+
+impl PwmLed {
+    /// Initializes a PWM-driven LED on channel B of a PWM slice.
+    ///
+    /// # Arguments
+    /// * `slice` - PWM slice owning the pin (`PWM_SLICE4` for GPIO25).
+    /// * `pin` - Channel B pin of `slice` (e.g. `PIN_25`).
+    /// * `frequency_hz` - PWM frequency, e.g. `DEFAULT_PWM_FREQUENCY_HZ`.
+    ///
+    /// # Returns
+    /// * `PwmLed` instance starting at brightness 0 (off).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let led = PwmLed::new(p.PWM_SLICE4, p.PIN_25, DEFAULT_PWM_FREQUENCY_HZ);
+    /// ```
+    pub fn new<T: Slice>(
+        slice: impl Peripheral<P = T> + 'static,
+        pin: impl Peripheral<P = impl ChannelBPin<T>> + 'static,
+        frequency_hz: u32,
+    ) -> Self {
+        let cycles = clk_sys_freq() / frequency_hz.max(1);
+        let divider = cycles
+            .div_ceil(u16::MAX as u32 + 1)
+            .clamp(1, u8::MAX as u32);
+        let top = (cycles / divider).clamp(2, u16::MAX as u32) - 1;
+
+        let mut config = Config::default();
+        config.divider = (divider as u8).into();
+        config.top = top as u16;
+        config.compare_b = 0;
+
+        let pwm = Pwm::new_output_b(slice, pin, config.clone());
+        Self {
+            pwm,
+            config,
+            brightness: 0,
+        }
+    }
+
+    /// Sets the LED brightness.
+    ///
+    /// # Arguments
+    /// * `level` - Brightness from `0` (off) to `255` (fully on).
+    ///
+    /// # Example
+    /// ```ignore
+    /// led.set_brightness(128);
+    /// ```
+    pub fn set_brightness(&mut self, level: u8) {
+        let top = self.config.top as u32 + 1;
+        self.config.compare_b = (top * level as u32 / u8::MAX as u32) as u16;
+        self.pwm.set_config(&self.config);
+        self.brightness = level;
+    }
+
+    /// Returns the current brightness level.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let level = led.brightness();
+    /// ```
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Ramps brightness up and down in a triangular curve.
+    ///
+    /// # Arguments
+    /// * `period` - Duration of one full dim-bright-dim cycle.
+    /// * `cycles` - Number of cycles to run; `0` breathes forever.
+    ///
+    /// # Behavior
+    /// - Each period is split into `BREATHE_STEPS` steps with a `Timer` wait
+    ///   between them.
+    /// - Leaves the LED off after a finite number of cycles.
+    ///
+    /// # Example
+    /// ```ignore
+    /// led.breathe(Duration::from_secs(2), 0).await;
+    /// ```
+    pub async fn breathe(&mut self, period: Duration, cycles: u32) {
+        let step_delay = period / BREATHE_STEPS;
+        let half = BREATHE_STEPS / 2;
+        let mut cycle = 0;
+
+        while cycles == 0 || cycle < cycles {
+            for step in 0..BREATHE_STEPS {
+                let distance = if step < half {
+                    step
+                } else {
+                    BREATHE_STEPS - step
+                };
+                self.set_brightness((distance * u8::MAX as u32 / half) as u8);
+                Timer::after(step_delay).await;
+            }
+            cycle = cycle.saturating_add(1);
+        }
+
+        self.set_brightness(0);
     }
 }
@@ -0,0 +1,13 @@
+//! # Rust Embassy Pico Project Library
+//!
+//! ## Features
+//! - Button, LED and run-cycle logic shared by the firmware binary.
+//! - Builds for the host as well, so the cycle logic can be unit tested
+//!   with `MockLed` and `MockButton`.
+
+#![cfg_attr(not(test), no_std)]
+
+pub mod button;
+pub mod config;
+pub mod led;
+pub mod run_cycle;
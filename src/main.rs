@@ -2,13 +2,11 @@
 #![no_main]
 
 use embassy_executor::Spawner;
+use embassy_rp::gpio::Pull;
 use embassy_rp::init;
+use rust_embassy_pico_project::{button, led, run_cycle};
 use {defmt_rtt as _, panic_probe as _};
 
-mod button;
-mod led;
-mod run_cycle;
-
 /// ## Main Entry Point
 ///
 /// Initializes peripherals and continuously runs the button-press cycle.
@@ -26,7 +24,7 @@ mod run_cycle;
 #[embassy_executor::main]
 async fn main(_spawner: Spawner) {
     let p = init(Default::default());
-    let mut button = button::init(p.PIN_16);
+    let mut button = button::Button::new(p.PIN_16, Pull::Up);
     let mut led = led::Led::new(p.PIN_25);
 
     loop {
@@ -1,41 +1,402 @@
 //! # Run Cycle Control Module
 //!
 //! ## Features
-//! - Waits for button press and release events.
+//! - Waits for debounced button press and release events.
 //! - Controls onboard LED (GPIO25).
 //! - Turns LED on when button pressed, off when released.
+//! - Reports how long the button was held.
+//! - Press-to-toggle mode as an alternative to momentary.
+//! - Distinguishes user activity from an idle timeout.
+//! - Finishes a cycle from an already-confirmed press.
+//! - Press counter that persists across cycles.
+//! - Non-blocking polled variant for cooperative loops.
 
-use crate::led::Led;
-use embassy_rp::gpio::Input;
-use embassy_time::Timer;
+#![allow(dead_code)]
+
+use crate::button::{self, ButtonInput};
+use crate::led::LedControl;
+use embassy_futures::select::{Either, select};
+use embassy_time::{Duration, Instant, Timer};
+
+/// Outcome of a single press-and-release cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct CycleResult {
+    /// Time between the debounced press and the debounced release.
+    pub held_for: Duration,
+}
+
+/// Whether the user pressed the button or the idle timeout elapsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Activity {
+    /// The button was pressed before the timeout.
+    Pressed,
+    /// The timeout elapsed with no press.
+    Idle,
+}
 
 /// Runs a full button press‐and‐release cycle.
 ///
 /// # Arguments
-/// * `led` - Mutable reference to the onboard LED controller.
-/// * `button` - Button input (GPIO16 with pull-up).
+/// * `led` - Mutable reference to the LED, real or `MockLed`.
+/// * `button` - Mutable reference to the button, real or `MockButton`.
 ///
 /// # Behavior
 /// - On press: Turns on the onboard LED.
 /// - On release: Turns off the onboard LED.
-/// - Includes a short delay for debounce after the cycle.
+/// - Press and release are debounced by the button's `DebounceConfig`.
+/// - With `Button`, a release only counts once the button stays released
+///   for the whole `DebounceConfig::release` window; if it bounces closed
+///   again, the LED stays on and the press continues.
+/// - LED behavior is identical for active-low and active-high buttons.
+///
+/// # Returns
+/// * `CycleResult` - How long the button was held.
 ///
 /// # Example
 /// ```ignore
-/// run_cycle(&mut led, &mut button).await;
+/// let result = run_cycle(&mut led, &mut button).await;
+/// if result.held_for > Duration::from_secs(2) { /* enter config mode */ }
 /// ```
-pub async fn run_cycle(led: &mut Led, button: &mut Input<'_>) {
-    if button.is_low() {
-        button.wait_for_high().await;
+pub async fn run_cycle<L: LedControl, B: ButtonInput>(led: &mut L, button: &mut B) -> CycleResult {
+    if button.is_pressed() {
+        button.wait_for_release().await;
     }
 
-    button.wait_for_low().await;
+    button.wait_for_press().await;
 
+    run_cycle_from_press(led, button).await
+}
+
+/// Finishes a cycle whose press has already been confirmed.
+///
+/// # Arguments
+/// * `led` - Mutable reference to the LED, real or `MockLed`.
+/// * `button` - Mutable reference to the button, real or `MockButton`.
+///
+/// # Behavior
+/// - Turns the LED on immediately, then waits for the release and turns
+///   it off.
+/// - Use it after `wait_press_or_idle` returns `Activity::Pressed`;
+///   `run_cycle` would instead treat the held button as a partial press
+///   and wait for another one.
+///
+/// # Returns
+/// * `CycleResult` - How long the button was held after entry.
+///
+/// # Example
+/// ```ignore
+/// if wait_press_or_idle(&mut button, Duration::from_secs(1)).await == Activity::Pressed {
+///     run_cycle_from_press(&mut led, &mut button).await;
+/// }
+/// ```
+pub async fn run_cycle_from_press<L: LedControl, B: ButtonInput>(
+    led: &mut L,
+    button: &mut B,
+) -> CycleResult {
     led.on();
+    let start = Instant::now();
+    defmt::info!("button: pressed");
 
-    button.wait_for_high().await;
+    button.wait_for_release().await;
 
     led.off();
+    let held_for = Instant::now() - start;
+    defmt::info!("button: released after {} ms", held_for.as_millis());
 
-    Timer::after_millis(10).await;
+    CycleResult { held_for }
+}
+
+/// Toggles the LED on each complete press-and-release.
+///
+/// # Arguments
+/// * `led` - Mutable reference to the LED, real or `MockLed`.
+/// * `button` - Mutable reference to the button, real or `MockButton`.
+///
+/// # Behavior
+/// - Waits for a debounced press and then a debounced release.
+/// - Flips the LED once per physical press, on release.
+/// - Leaves the LED in its new state when it returns.
+///
+/// # Example
+/// ```ignore
+/// loop {
+///     run_cycle_toggle(&mut led, &mut button).await;
+/// }
+/// ```
+pub async fn run_cycle_toggle<L: LedControl, B: ButtonInput>(led: &mut L, button: &mut B) {
+    if button.is_pressed() {
+        button.wait_for_release().await;
+    }
+
+    button.wait_for_press().await;
+    button.wait_for_release().await;
+
+    led.toggle();
+    defmt::info!("button: toggled led, on = {}", led.is_on());
+}
+
+/// Waits for a button press or an idle timeout, whichever comes first.
+///
+/// # Arguments
+/// * `button` - Mutable reference to the button, real or `MockButton`.
+/// * `idle` - Time without a press after which the user is considered idle.
+///
+/// # Returns
+/// * `Activity::Pressed` - A debounced press occurred (a button already held
+///   at entry counts as a press).
+/// * `Activity::Idle` - `idle` elapsed first.
+///
+/// # Example
+/// ```ignore
+/// if wait_press_or_idle(&mut button, Duration::from_secs(30)).await == Activity::Idle {
+///     // start screensaver
+/// }
+/// ```
+pub async fn wait_press_or_idle<B: ButtonInput>(button: &mut B, idle: Duration) -> Activity {
+    match select(button.wait_for_press(), Timer::after(idle)).await {
+        Either::First(()) => Activity::Pressed,
+        Either::Second(()) => Activity::Idle,
+    }
+}
+
+/// Counts complete presses across cycles and fires a callback at a target.
+pub struct PressCounter<F: FnMut(u32)> {
+    count: u32,
+    threshold: u32,
+    on_threshold: F,
+}
+
+impl<F: FnMut(u32)> PressCounter<F> {
+    /// Creates a counter starting at zero.
+    ///
+    /// # Arguments
+    /// * `threshold` - Count at which `on_threshold` is called.
+    /// * `on_threshold` - Callback invoked with the count when it reaches
+    ///   `threshold`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut counter = PressCounter::new(3, |_| unlocked = true);
+    /// ```
+    pub fn new(threshold: u32, on_threshold: F) -> Self {
+        Self {
+            count: 0,
+            threshold,
+            on_threshold,
+        }
+    }
+
+    /// Waits for one full press and release, then increments the count.
+    ///
+    /// # Arguments
+    /// * `button` - Mutable reference to the button, real or `MockButton`.
+    ///
+    /// # Returns
+    /// * `true` - This press brought the count to the threshold.
+    /// * `false` - Otherwise.
+    ///
+    /// # Behavior
+    /// - If the button is already held at entry, waits for release first.
+    /// - The callback fires once when the count reaches the threshold; call
+    ///   `reset` to arm it again.
+    ///
+    /// # Example
+    /// ```ignore
+    /// counter.tick(&mut button).await;
+    /// ```
+    pub async fn tick<B: ButtonInput>(&mut self, button: &mut B) -> bool {
+        if button.is_pressed() {
+            button.wait_for_release().await;
+        }
+
+        button.wait_for_press().await;
+        button.wait_for_release().await;
+
+        self.count = self.count.saturating_add(1);
+        if self.count == self.threshold {
+            (self.on_threshold)(self.count);
+            return true;
+        }
+        false
+    }
+
+    /// Returns the number of presses counted since the last reset.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let presses = counter.count();
+    /// ```
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Resets the count to zero.
+    ///
+    /// # Example
+    /// ```ignore
+    /// counter.reset();
+    /// ```
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+/// Debounce state carried between calls to `PollState::poll`.
+pub struct PollState {
+    pressed: bool,
+    candidate: bool,
+    since: Instant,
+    debounce: Duration,
+}
+
+impl PollState {
+    /// Creates a released poll state.
+    ///
+    /// # Arguments
+    /// * `debounce` - Time a new reading must persist before it is accepted.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut state = PollState::new(Duration::from_millis(20));
+    /// ```
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            pressed: false,
+            candidate: false,
+            since: Instant::now(),
+            debounce,
+        }
+    }
+
+    /// Reads the button once and mirrors its debounced state on the LED.
+    ///
+    /// # Arguments
+    /// * `led` - Mutable reference to the LED, real or `MockLed`.
+    /// * `button` - Reference to the button, real or `MockButton`.
+    ///
+    /// # Returns
+    /// * `bool` - Debounced pressed state after this poll.
+    ///
+    /// # Behavior
+    /// - Never awaits; call it in a loop with other work interleaved.
+    /// - A changed reading only takes effect once it has been seen on
+    ///   every poll for at least the debounce window.
+    /// - The LED is only written when the debounced state changes.
+    ///
+    /// # Example
+    /// ```ignore
+    /// loop {
+    ///     state.poll(&mut led, &button);
+    ///     do_other_work().await;
+    /// }
+    /// ```
+    pub fn poll<L: LedControl, B: ButtonInput>(&mut self, led: &mut L, button: &B) -> bool {
+        let now = Instant::now();
+        let reading = button.is_pressed();
+
+        if reading != self.candidate {
+            self.candidate = reading;
+            self.since = now;
+        } else if reading != self.pressed && now - self.since >= self.debounce {
+            self.pressed = reading;
+            if reading {
+                led.on();
+            } else {
+                led.off();
+            }
+        }
+
+        self.pressed
+    }
+}
+
+impl Default for PollState {
+    /// Uses `button::DEFAULT_DEBOUNCE_MS` as the debounce window.
+    fn default() -> Self {
+        Self::new(button::default_debounce())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::{ButtonPolarity, MockButton};
+    use crate::led::MockLed;
+    use defmt_rtt as _;
+    use embassy_futures::block_on;
+    use embassy_rp::gpio::Level;
+
+    #[defmt::panic_handler]
+    fn defmt_panic() -> ! {
+        panic!("defmt panic")
+    }
+
+    #[test]
+    fn run_cycle_lights_led_while_pressed() {
+        let mut led = MockLed::new();
+        let mut button = MockButton::new(&[true, false]);
+
+        block_on(run_cycle(&mut led, &mut button));
+
+        assert_eq!(led.transitions(), &[true, false]);
+    }
+
+    #[test]
+    fn run_cycle_ignores_press_held_at_entry() {
+        let mut led = MockLed::new();
+        let mut button = MockButton::new(&[true, false, true, false]);
+        block_on(button.wait_for_press());
+
+        block_on(run_cycle(&mut led, &mut button));
+
+        assert_eq!(led.transitions(), &[true, false]);
+        // Both presses were consumed, so no press is left in the script.
+        assert_eq!(
+            block_on(wait_press_or_idle(&mut button, Duration::from_millis(10))),
+            Activity::Idle
+        );
+    }
+
+    #[test]
+    fn run_cycle_from_press_lights_led_for_pending_press() {
+        let mut led = MockLed::new();
+        let mut button = MockButton::new(&[true, false]);
+
+        block_on(async {
+            let activity = wait_press_or_idle(&mut button, Duration::from_secs(1)).await;
+            assert_eq!(activity, Activity::Pressed);
+            run_cycle_from_press(&mut led, &mut button).await;
+        });
+
+        assert_eq!(led.transitions(), &[true, false]);
+    }
+
+    #[test]
+    fn run_cycle_matches_for_active_low_and_active_high() {
+        let mut low_led = MockLed::new();
+        let mut low_button =
+            MockButton::with_polarity(&[Level::Low, Level::High], ButtonPolarity::ActiveLow);
+        block_on(run_cycle(&mut low_led, &mut low_button));
+
+        let mut high_led = MockLed::new();
+        let mut high_button =
+            MockButton::with_polarity(&[Level::High, Level::Low], ButtonPolarity::ActiveHigh);
+        block_on(run_cycle(&mut high_led, &mut high_button));
+
+        assert_eq!(low_led.transitions(), &[true, false]);
+        assert_eq!(low_led.transitions(), high_led.transitions());
+    }
+
+    #[test]
+    fn run_cycle_toggle_flips_led_once_per_click() {
+        let mut led = MockLed::new();
+        let mut button = MockButton::new(&[true, false, true, false]);
+
+        block_on(run_cycle_toggle(&mut led, &mut button));
+        assert!(led.is_on());
+
+        block_on(run_cycle_toggle(&mut led, &mut button));
+        assert!(!led.is_on());
+        assert_eq!(led.transitions(), &[true, false]);
+    }
 }